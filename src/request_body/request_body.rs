@@ -1,10 +1,12 @@
 use super::hurl_json_building::{
     build_json_list_space, build_json_list_value, build_json_object_element,
 };
-use hurl_core::ast::Body;
+use std::collections::HashSet;
+
+use hurl_core::ast::{Body, FileParam, FileValue, KeyValue, MultipartParam, Section, SectionValue};
 use log::debug;
 use oas3::{
-    spec::{ObjectOrReference, RefError, RequestBody},
+    spec::{Discriminator, ObjectOrReference, RefError, RequestBody},
     Schema, Spec,
 };
 
@@ -15,63 +17,380 @@ use crate::{
 
 use super::body::{parse_schema, template_from_string};
 
+/// Which object properties to include when building a generated JSON body.
+/// Form, multipart and XML bodies always include every property.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BodyFields {
+    /// Emit every property declared on the schema.
+    All,
+    /// Emit only properties listed in the schema's `required` array.
+    RequiredOnly,
+}
+
 pub struct SpecBodySettings {
     pub formatting: Formatting,
+    /// When a request body advertises more than one content type, try this
+    /// one first (matched case-insensitively) before falling back to the
+    /// first content type we know how to generate.
+    pub preferred_content_type: Option<String>,
+    pub body_fields: BodyFields,
+    /// Caps how many levels of nested `properties`/`items` generation will
+    /// recurse into, so deeply (but non-cyclically) nested schemas can't
+    /// blow up the generated body either. `allOf`/`oneOf`/`anyOf` branches
+    /// are generated at the level of the schema that holds them.
+    pub max_depth: usize,
+    /// When a oneOf/anyOf body has a discriminator, generate the variant
+    /// matching this value (a discriminator mapping key, or the referenced
+    /// schema's name) instead of the default choice.
+    pub variant_selector: Option<String>,
+    /// How scalar leaf values (numbers, strings, enum picks) are generated.
+    pub value_generation: ValueGeneration,
 }
 
+/// How scalar leaf values (numbers, strings, enum picks) are generated in a
+/// JSON body. Form, multipart and XML bodies always use the fixed placeholders.
+#[derive(Clone)]
+pub enum ValueGeneration {
+    /// Always emit the same fixed placeholder for a given type/format.
+    Fixed,
+    /// Emit randomized-but-reproducible values: each leaf is seeded from
+    /// `seed` plus its property path, so re-running generation against the
+    /// same spec and seed always produces the same body.
+    Seeded(u64),
+}
+
+const DEFAULT_MAX_DEPTH: usize = 20;
+
 impl SpecBodySettings {
     pub fn from_settings(settings: &Settings) -> Self {
         Self {
             formatting: settings.formatting.clone(),
+            preferred_content_type: None,
+            body_fields: BodyFields::All,
+            max_depth: DEFAULT_MAX_DEPTH,
+            variant_selector: None,
+            value_generation: ValueGeneration::Fixed,
         }
     }
+
+    /// Whether `key` should be emitted given the current `body_fields` mode,
+    /// consulting `required` (a schema's or an accumulated `allOf`'s
+    /// required-property list) when the mode is `RequiredOnly`.
+    fn includes_field(&self, key: &str, required: &[String]) -> bool {
+        match self.body_fields {
+            BodyFields::All => true,
+            BodyFields::RequiredOnly => required.iter().any(|r| r == key),
+        }
+    }
+}
+
+/// The request-level artifact generated from an OpenAPI request body.
+///
+/// Most content types (JSON, XML) map onto a single Hurl `Body`, but form
+/// data maps onto a Hurl section (`[FormParams]` / `[MultipartFormData]`)
+/// instead. Every caller of `from_spec_body` must match on both variants
+/// and place them accordingly (a `Body` as the request's body, a `Section`
+/// alongside the request's other sections) — treating this as if it were
+/// still always a `Body` silently drops form/multipart request bodies.
+pub enum SpecBody {
+    Body(Body),
+    Section(Section),
 }
 
 pub fn from_spec_body(
     spec_body: RequestBody,
     spec: &Spec,
     settings: SpecBodySettings,
-) -> Result<Option<Body>, RefError> {
-    for content in spec_body.content {
-        let schema = match parse_schema(content.1.schema, spec)? {
+) -> Result<Option<SpecBody>, RefError> {
+    let mut contents: Vec<_> = spec_body.content.into_iter().collect();
+
+    if let Some(preferred) = &settings.preferred_content_type {
+        if let Some(index) = contents
+            .iter()
+            .position(|(content_type, _)| content_type.eq_ignore_ascii_case(preferred))
+        {
+            let preferred_content = contents.remove(index);
+            contents.insert(0, preferred_content);
+        }
+    }
+
+    for (content_type, media_type) in contents {
+        let schema = match parse_schema(media_type.schema, spec)? {
             Some(s) => s,
             None => continue,
         };
 
-        // TODO: implement support for other types and choose types
-        if content.0.to_lowercase().contains("json") {
-            return match parse_json_from_schema(schema, spec, 1, &settings)? {
-                Some(v) => Ok(Some(Body {
+        let content_type = content_type.to_lowercase();
+
+        if content_type.contains("json") {
+            return match parse_json_from_schema(
+                schema,
+                spec,
+                1,
+                "body",
+                &settings,
+                &mut RefStack::new(),
+            )? {
+                Some(v) => Ok(Some(SpecBody::Body(Body {
                     line_terminators: vec![],
                     space0: empty_space(),
                     value: hurl_core::ast::Bytes::Json(v),
                     line_terminator0: newline(),
-                })),
+                }))),
                 None => Ok(None),
             };
         }
+
+        if content_type.contains("x-www-form-urlencoded") {
+            return Ok(Some(SpecBody::Section(form_params_section(
+                &schema, spec,
+            )?)));
+        }
+
+        if content_type.contains("multipart") {
+            return Ok(Some(SpecBody::Section(multipart_section(&schema, spec)?)));
+        }
+
+        if content_type.contains("xml") {
+            return Ok(Some(SpecBody::Body(Body {
+                line_terminators: vec![],
+                space0: empty_space(),
+                value: hurl_core::ast::Bytes::Xml(xml_string_from_schema(
+                    &schema, spec, "root", &settings,
+                )?),
+                line_terminator0: newline(),
+            })));
+        }
     }
 
     Ok(None)
 }
 
+/// A schema's properties, ordered by name rather than however the
+/// underlying map happens to iterate, so generated bodies don't depend on
+/// whether `oas3` backs `Schema::properties` with an order-preserving map
+/// or a hash map — needed for the "same seed, same spec, same body"
+/// guarantee `ValueGeneration::Seeded` promises, and for stable output in
+/// general.
+fn properties_in_stable_order(schema: &Schema) -> Vec<(String, ObjectOrReference<Schema>)> {
+    let mut properties: Vec<_> = schema.properties.clone().into_iter().collect();
+    properties.sort_by(|a, b| a.0.cmp(&b.0));
+    properties
+}
+
+/// Walks an object schema's properties into a `[FormParams]` section,
+/// one `key=value` pair per property.
+fn form_params_section(schema: &Schema, spec: &Spec) -> Result<Section, RefError> {
+    let mut key_values = vec![];
+
+    for (name, prop) in properties_in_stable_order(schema) {
+        let prop_schema = prop.resolve(spec)?;
+
+        // Nested objects/arrays have no single scalar rendering a form field
+        // can carry, so they are left out rather than stringified.
+        if let Some(value) = scalar_string_from_schema(&prop_schema) {
+            key_values.push(build_key_value(&name, &value));
+        }
+    }
+
+    Ok(Section {
+        line_terminators: vec![],
+        space0: empty_space(),
+        line_terminator0: newline(),
+        value: SectionValue::FormParams(key_values),
+    })
+}
+
+/// Walks an object schema's properties into a `[MultipartFormData]`
+/// section, treating `format: binary` properties as file uploads.
+fn multipart_section(schema: &Schema, spec: &Spec) -> Result<Section, RefError> {
+    let mut params = vec![];
+
+    for (name, prop) in properties_in_stable_order(schema) {
+        let prop_schema = prop.resolve(spec)?;
+
+        if prop_schema.format.as_deref() == Some("binary") {
+            params.push(MultipartParam::FileParam(FileParam {
+                line_terminators: vec![],
+                space0: empty_space(),
+                key: template_from_string(&name),
+                space1: empty_space(),
+                space2: empty_space(),
+                value: FileValue {
+                    space0: empty_space(),
+                    filename: template_from_string(&format!("{}.txt", name)),
+                    space1: empty_space(),
+                    space2: empty_space(),
+                    content_type: None,
+                },
+                line_terminator0: newline(),
+            }));
+        } else if let Some(value) = scalar_string_from_schema(&prop_schema) {
+            params.push(MultipartParam::Param(build_key_value(&name, &value)));
+        }
+    }
+
+    Ok(Section {
+        line_terminators: vec![],
+        space0: empty_space(),
+        line_terminator0: newline(),
+        value: SectionValue::MultipartFormData(params),
+    })
+}
+
+/// Serializes a schema tree to an XML string body, using property names as
+/// element tags and `root_tag` as the document's root element.
+fn xml_string_from_schema(
+    schema: &Schema,
+    spec: &Spec,
+    root_tag: &str,
+    settings: &SpecBodySettings,
+) -> Result<String, RefError> {
+    let mut body = String::new();
+    write_xml_element(schema, spec, root_tag, &mut body, settings, 1, &mut RefStack::new())?;
+    Ok(body)
+}
+
+fn write_xml_element(
+    schema: &Schema,
+    spec: &Spec,
+    tag: &str,
+    out: &mut String,
+    settings: &SpecBodySettings,
+    depth: usize,
+    active_refs: &mut RefStack,
+) -> Result<(), RefError> {
+    if depth > settings.max_depth {
+        out.push_str(&format!("<{tag}></{tag}>", tag = tag));
+        return Ok(());
+    }
+
+    if schema.properties.is_empty() {
+        out.push_str(&format!(
+            "<{tag}>{value}</{tag}>",
+            tag = tag,
+            value = scalar_string_from_schema(schema).unwrap_or_default()
+        ));
+        return Ok(());
+    }
+
+    out.push_str(&format!("<{}>", tag));
+    for (name, prop) in properties_in_stable_order(schema) {
+        match resolve_for_recursion(prop, spec, active_refs)? {
+            Some((prop_schema, ref_path)) => {
+                write_xml_element(&prop_schema, spec, &name, out, settings, depth + 1, active_refs)?;
+                leave_ref(active_refs, ref_path);
+            }
+            // A cycle: the $ref is already being resolved higher up the
+            // stack, so stop here instead of recursing into it again.
+            None => out.push_str(&format!("<{tag}></{tag}>", tag = name)),
+        }
+    }
+    out.push_str(&format!("</{}>", tag));
+
+    Ok(())
+}
+
+/// Renders a scalar (boolean/integer/number/string) schema to a plain
+/// string, reusing the same constraint-aware placeholders as JSON
+/// generation. Returns `None` for object/array (and untyped, which the
+/// JSON generator also treats as an object) schemas, which have no single
+/// scalar rendering and must be flattened or skipped by the caller instead.
+fn scalar_string_from_schema(schema: &Schema) -> Option<String> {
+    if let Some(enum_value) = schema.enum_values.first() {
+        return Some(match enum_value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        });
+    }
+
+    match schema.schema_type {
+        Some(oas3::spec::SchemaType::Boolean) => Some("true".to_string()),
+        Some(oas3::spec::SchemaType::Integer) => Some(integer_within_bounds(schema, 3).to_string()),
+        Some(oas3::spec::SchemaType::Number) => Some(number_within_bounds(schema, 3.3).to_string()),
+        Some(oas3::spec::SchemaType::String) => Some(string_value_for_schema(schema)),
+        Some(oas3::spec::SchemaType::Object) | Some(oas3::spec::SchemaType::Array) | None => None,
+    }
+}
+
+fn build_key_value(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        line_terminators: vec![],
+        space0: empty_space(),
+        key: template_from_string(key),
+        space1: empty_space(),
+        space2: empty_space(),
+        value: template_from_string(value),
+        line_terminator0: newline(),
+    }
+}
+
+/// `$ref` names currently on the resolution stack, used to detect a
+/// self-referential schema before recursing into it again.
+type RefStack = HashSet<String>;
+
+/// Resolves `obj`, unless doing so would recurse into a `$ref` that is
+/// already being resolved higher up the stack (a cycle), in which case
+/// `None` is returned instead. Callers that get `Some` back must remove the
+/// ref from `active_refs` once they are done recursing into it (see
+/// `leave_ref`).
+fn resolve_for_recursion(
+    obj: ObjectOrReference<Schema>,
+    spec: &Spec,
+    active_refs: &mut RefStack,
+) -> Result<Option<(Schema, Option<String>)>, RefError> {
+    let ref_path = match &obj {
+        ObjectOrReference::Ref { ref_path } => Some(ref_path.clone()),
+        ObjectOrReference::Object(_) => None,
+    };
+
+    if let Some(ref_path) = &ref_path {
+        if !active_refs.insert(ref_path.clone()) {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((obj.resolve(spec)?, ref_path)))
+}
+
+fn leave_ref(active_refs: &mut RefStack, ref_path: Option<String>) {
+    if let Some(ref_path) = ref_path {
+        active_refs.remove(&ref_path);
+    }
+}
+
 fn parse_json_from_schema(
     schema: Schema,
     spec: &Spec,
     depth: usize,
+    path: &str,
     settings: &SpecBodySettings,
+    active_refs: &mut RefStack,
 ) -> Result<Option<hurl_core::ast::JsonValue>, RefError> {
     if schema.read_only.unwrap_or(false) {
         return Ok(None);
     }
 
+    if depth > settings.max_depth {
+        return Ok(Some(hurl_core::ast::JsonValue::Null));
+    }
+
     match schema.example {
-        Some(ex) => return Ok(Some(serde_to_hurl_json(&ex, depth, settings))),
+        Some(ex) => {
+            return Ok(Some(serde_to_hurl_json(
+                &ex,
+                depth,
+                settings,
+                Some(&schema.required),
+            )))
+        }
         None => (),
     }
 
     let default_val = match schema.schema_type {
-        Some(t) => Some(default_json_value_from_schema_type(t)),
+        Some(_) => Some(default_json_value_from_schema_type(
+            &schema, depth, path, settings,
+        )),
         None => None,
     };
 
@@ -81,9 +400,23 @@ fn parse_json_from_schema(
                 SimpleJsonValue::Scalar(s) => Ok(Some(s)),
                 SimpleJsonValue::Array => match schema.items {
                     Some(items_schema) => {
-                        let schema = match items_schema.resolve(spec) {
-                            Ok(s) => parse_json_from_schema(s, spec, depth, settings)?,
-                            Err(e) => return Err(e),
+                        let resolved = resolve_for_recursion(items_schema, spec, active_refs)?;
+
+                        let schema = match resolved {
+                            Some((s, ref_path)) => {
+                                let item_path = format!("{}[]", path);
+                                let v = parse_json_from_schema(
+                                    s,
+                                    spec,
+                                    depth + 1,
+                                    &item_path,
+                                    settings,
+                                    active_refs,
+                                )?;
+                                leave_ref(active_refs, ref_path);
+                                v
+                            }
+                            None => None,
                         };
 
                         Ok(Some(hurl_core::ast::JsonValue::List {
@@ -102,13 +435,28 @@ fn parse_json_from_schema(
                 SimpleJsonValue::Object => {
                     let mut props = vec![];
 
-                    for prop in schema.properties {
-                        let val = parse_json_from_schema(
-                            prop.1.resolve(spec)?,
-                            spec,
-                            depth + 1,
-                            settings,
-                        )?;
+                    for prop in properties_in_stable_order(&schema) {
+                        if !settings.includes_field(&prop.0, &schema.required) {
+                            continue;
+                        }
+
+                        let resolved = resolve_for_recursion(prop.1, spec, active_refs)?;
+                        let prop_path = format!("{}.{}", path, prop.0);
+                        let val = match resolved {
+                            Some((s, ref_path)) => {
+                                let v = parse_json_from_schema(
+                                    s,
+                                    spec,
+                                    depth + 1,
+                                    &prop_path,
+                                    settings,
+                                    active_refs,
+                                )?;
+                                leave_ref(active_refs, ref_path);
+                                v
+                            }
+                            None => Some(hurl_core::ast::JsonValue::Null),
+                        };
                         match val {
                             Some(v) => props.push(build_json_object_element(
                                 template_from_string(&prop.0),
@@ -133,17 +481,35 @@ fn parse_json_from_schema(
                     schema.all_of,
                     spec,
                     depth,
+                    path,
                     settings,
+                    active_refs,
                 )?));
             }
 
             if schema.one_of.len() > 0 {
-                return Ok(json_obj_from_anyof(schema.one_of, spec, depth, &settings)?);
+                return Ok(json_obj_from_anyof(
+                    schema.one_of,
+                    spec,
+                    depth,
+                    path,
+                    settings,
+                    active_refs,
+                    schema.discriminator.as_ref(),
+                )?);
             }
 
             // Treat any_of and one_of the same / use only the first schema of both
             if schema.any_of.len() > 0 {
-                return Ok(json_obj_from_anyof(schema.any_of, spec, depth, &settings)?);
+                return Ok(json_obj_from_anyof(
+                    schema.any_of,
+                    spec,
+                    depth,
+                    path,
+                    settings,
+                    active_refs,
+                    schema.discriminator.as_ref(),
+                )?);
             }
 
             debug!("Couldn't build anything from schema. Returning null...");
@@ -157,6 +523,7 @@ fn serde_to_hurl_json(
     serde_val: &serde_json::Value,
     depth: usize,
     settings: &SpecBodySettings,
+    required: Option<&Vec<String>>,
 ) -> hurl_core::ast::JsonValue {
     match serde_val {
         serde_json::Value::Null => hurl_core::ast::JsonValue::Null,
@@ -169,20 +536,27 @@ fn serde_to_hurl_json(
                 .iter()
                 .map(|el| {
                     build_json_list_value(
-                        serde_to_hurl_json(el, depth, settings),
+                        serde_to_hurl_json(el, depth, settings, None),
                         &settings.formatting,
                     )
                 })
                 .collect(),
         },
+        // `required` only describes the schema of this object itself, not
+        // any nested object literals baked into the example, so it is only
+        // honoured at this level and dropped on recursion.
         serde_json::Value::Object(o) => hurl_core::ast::JsonValue::Object {
             space0: "".to_string(),
             elements: o
                 .into_iter()
+                .filter(|prop| match required {
+                    Some(required) => settings.includes_field(&prop.0, required),
+                    None => true,
+                })
                 .map(|prop| {
                     build_json_object_element(
                         template_from_string(&prop.0),
-                        serde_to_hurl_json(prop.1, depth, settings),
+                        serde_to_hurl_json(prop.1, depth, settings, None),
                         depth,
                         &settings.formatting,
                     )
@@ -198,51 +572,414 @@ enum SimpleJsonValue {
     Object,
 }
 
-fn default_json_value_from_schema_type(schema_type: oas3::spec::SchemaType) -> SimpleJsonValue {
-    match schema_type {
-        oas3::spec::SchemaType::Boolean => {
+fn default_json_value_from_schema_type(
+    schema: &Schema,
+    depth: usize,
+    path: &str,
+    settings: &SpecBodySettings,
+) -> SimpleJsonValue {
+    // An enum constrains the accepted values more tightly than the bare type,
+    // so honour it before falling back to a type-based placeholder.
+    if let Some(enum_value) = pick_enum_value(schema, path, settings) {
+        return SimpleJsonValue::Scalar(serde_to_hurl_json(enum_value, depth, settings, None));
+    }
+
+    match schema.schema_type {
+        Some(oas3::spec::SchemaType::Boolean) => {
             SimpleJsonValue::Scalar(hurl_core::ast::JsonValue::Boolean(true))
         }
-        oas3::spec::SchemaType::Integer => {
-            SimpleJsonValue::Scalar(hurl_core::ast::JsonValue::Number(3.to_string()))
-        }
-        oas3::spec::SchemaType::Number => {
-            SimpleJsonValue::Scalar(hurl_core::ast::JsonValue::Number(3.3.to_string()))
+        Some(oas3::spec::SchemaType::Integer) => SimpleJsonValue::Scalar(
+            hurl_core::ast::JsonValue::Number(integer_value(schema, path, settings, 3).to_string()),
+        ),
+        Some(oas3::spec::SchemaType::Number) => {
+            SimpleJsonValue::Scalar(hurl_core::ast::JsonValue::Number(
+                number_value(schema, path, settings, 3.3).to_string(),
+            ))
         }
-        oas3::spec::SchemaType::String => SimpleJsonValue::Scalar(
-            hurl_core::ast::JsonValue::String(template_from_string(&"string".to_string())),
+        Some(oas3::spec::SchemaType::String) => SimpleJsonValue::Scalar(
+            hurl_core::ast::JsonValue::String(template_from_string(&string_value(
+                schema, path, settings,
+            ))),
         ),
-        oas3::spec::SchemaType::Array => SimpleJsonValue::Array,
-        oas3::spec::SchemaType::Object => SimpleJsonValue::Object,
+        Some(oas3::spec::SchemaType::Array) => SimpleJsonValue::Array,
+        Some(oas3::spec::SchemaType::Object) | None => SimpleJsonValue::Object,
+    }
+}
+
+/// Picks an enum value: the first one in `Fixed` mode, a seeded random pick
+/// in `Seeded` mode.
+fn pick_enum_value<'a>(
+    schema: &'a Schema,
+    path: &str,
+    settings: &SpecBodySettings,
+) -> Option<&'a serde_json::Value> {
+    match settings.value_generation {
+        ValueGeneration::Fixed => schema.enum_values.first(),
+        ValueGeneration::Seeded(seed) if !schema.enum_values.is_empty() => {
+            let mut rng = SplitMix64::from_seed_and_path(seed, path);
+            let index = rng.gen_range_i64(0, schema.enum_values.len() as i64 - 1) as usize;
+            schema.enum_values.get(index)
+        }
+        ValueGeneration::Seeded(_) => None,
+    }
+}
+
+/// Picks an integer leaf value per the configured `ValueGeneration` mode.
+fn integer_value(schema: &Schema, path: &str, settings: &SpecBodySettings, fallback: i64) -> i64 {
+    match settings.value_generation {
+        ValueGeneration::Fixed => integer_within_bounds(schema, fallback),
+        ValueGeneration::Seeded(seed) => {
+            let min = schema.minimum.map(|m| m as i64).unwrap_or(0);
+            let max = schema.maximum.map(|m| m as i64).unwrap_or(min + 1000);
+            SplitMix64::from_seed_and_path(seed, path).gen_range_i64(min, max)
+        }
+    }
+}
+
+/// Picks a number leaf value per the configured `ValueGeneration` mode.
+fn number_value(schema: &Schema, path: &str, settings: &SpecBodySettings, fallback: f64) -> f64 {
+    match settings.value_generation {
+        ValueGeneration::Fixed => number_within_bounds(schema, fallback),
+        ValueGeneration::Seeded(seed) => {
+            let min = schema.minimum.unwrap_or(0.0);
+            let max = schema.maximum.unwrap_or(min + 1000.0).max(min);
+            let fraction = (SplitMix64::from_seed_and_path(seed, path).next_u64() % 10_000) as f64
+                / 10_000.0;
+            min + fraction * (max - min)
+        }
+    }
+}
+
+/// Picks a string leaf value per the configured `ValueGeneration` mode.
+/// Formatted strings (dates, emails, UUIDs, ...) keep their fixed shape even
+/// when seeded, since randomizing them would produce invalid data.
+fn string_value(schema: &Schema, path: &str, settings: &SpecBodySettings) -> String {
+    match settings.value_generation {
+        ValueGeneration::Fixed => string_value_for_schema(schema),
+        ValueGeneration::Seeded(_) if schema.format.is_some() => string_value_for_schema(schema),
+        ValueGeneration::Seeded(seed) => {
+            let mut rng = SplitMix64::from_seed_and_path(seed, path);
+            let min = schema.min_length.unwrap_or(5);
+            let max = schema.max_length.unwrap_or(min + 10).max(min);
+            let len = rng.gen_len(min, max);
+            pattern_aware_string(schema, &mut rng, len)
+        }
     }
 }
 
+/// A small, dependency-free PRNG (SplitMix64) used only to turn a seed plus
+/// a property path into a reproducible sequence of numbers: re-running
+/// generation with the same seed against the same spec always walks the
+/// same paths in the same order, so it always produces the same values.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn from_seed_and_path(seed: u64, path: &str) -> Self {
+        let mut hash = seed ^ 0xcbf29ce484222325;
+        for byte in path.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        Self(hash)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn gen_range_i64(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        let span = (max - min) as u64 + 1;
+        min + (self.next_u64() % span) as i64
+    }
+
+    fn gen_len(&mut self, min: usize, max: usize) -> usize {
+        if max <= min {
+            return min;
+        }
+        min + (self.next_u64() as usize % (max - min + 1))
+    }
+
+    fn gen_alphanumeric(&mut self, len: usize) -> String {
+        const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        (0..len)
+            .map(|_| CHARS[self.next_u64() as usize % CHARS.len()] as char)
+            .collect()
+    }
+
+    fn gen_digits(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(b'0' + (self.next_u64() % 10) as u8))
+            .collect()
+    }
+}
+
+/// Generates a string matching a simple subset of `pattern` (digit-only or
+/// lowercase-letter-only character classes) when recognised, otherwise an
+/// alphanumeric string, at the given length.
+fn pattern_aware_string(schema: &Schema, rng: &mut SplitMix64, len: usize) -> String {
+    match schema.pattern.as_deref() {
+        Some(p) if p.contains("[0-9]") || p.contains("\\d") => rng.gen_digits(len),
+        Some(p) if p.contains("[a-z]") => (0..len)
+            .map(|_| char::from(b'a' + (rng.next_u64() % 26) as u8))
+            .collect(),
+        _ => rng.gen_alphanumeric(len),
+    }
+}
+
+/// Picks an integer inside `[minimum, maximum]` when the schema declares
+/// either bound, defaulting to `minimum` when both are present.
+fn integer_within_bounds(schema: &Schema, fallback: i64) -> i64 {
+    match (schema.minimum, schema.maximum) {
+        (Some(min), _) => min as i64,
+        (None, Some(max)) if (max as i64) < fallback => max as i64,
+        _ => fallback,
+    }
+}
+
+/// Picks a number inside `[minimum, maximum]` when the schema declares
+/// either bound, defaulting to `minimum` when both are present.
+fn number_within_bounds(schema: &Schema, fallback: f64) -> f64 {
+    match (schema.minimum, schema.maximum) {
+        (Some(min), _) => min,
+        (None, Some(max)) if max < fallback => max,
+        _ => fallback,
+    }
+}
+
+/// Builds a placeholder string honouring `format`, then pads/truncates it to
+/// fit within `minLength`/`maxLength` when they are declared.
+fn string_value_for_schema(schema: &Schema) -> String {
+    let base = match schema.format.as_deref() {
+        Some("date-time") => "2024-01-01T00:00:00Z".to_string(),
+        Some("date") => "2024-01-01".to_string(),
+        Some("uuid") => "00000000-0000-0000-0000-000000000000".to_string(),
+        Some("email") => "user@example.com".to_string(),
+        Some("byte") => "aGVsbG8=".to_string(),
+        Some("uri") => "https://example.com".to_string(),
+        _ => "string".to_string(),
+    };
+
+    pad_or_truncate_string(base, schema.min_length, schema.max_length)
+}
+
+fn pad_or_truncate_string(
+    mut value: String,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+) -> String {
+    if let Some(max) = max_length {
+        if value.len() > max {
+            value.truncate(max);
+        }
+    }
+
+    if let Some(min) = min_length {
+        while value.len() < min {
+            value.push('x');
+        }
+    }
+
+    value
+}
+
 fn json_obj_from_anyof(
     anyof: Vec<ObjectOrReference<Schema>>,
     spec: &Spec,
     depth: usize,
+    path: &str,
     settings: &SpecBodySettings,
+    active_refs: &mut RefStack,
+    discriminator: Option<&Discriminator>,
 ) -> Result<Option<hurl_core::ast::JsonValue>, RefError> {
-    for schema in &anyof {
-        return parse_json_from_schema(schema.resolve(spec)?, spec, depth, &settings);
+    let (variant, discriminator_value) = match select_variant(&anyof, discriminator, settings) {
+        Some((obj, discriminator_value)) => (obj.clone(), discriminator_value),
+        None => {
+            return Ok(Some(hurl_core::ast::JsonValue::Object {
+                space0: "".to_string(),
+                elements: vec![],
+            }))
+        }
+    };
+
+    let (schema, ref_path) = match resolve_for_recursion(variant, spec, active_refs)? {
+        Some(pair) => pair,
+        None => return Ok(Some(hurl_core::ast::JsonValue::Null)),
+    };
+
+    let value = parse_json_from_schema(schema, spec, depth, path, settings, active_refs)?;
+    leave_ref(active_refs, ref_path);
+
+    let value = match value {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    let value = match (discriminator, discriminator_value) {
+        (Some(d), Some(discriminator_value)) => {
+            set_discriminator_property(value, &d.property_name, &discriminator_value, depth, settings)
+        }
+        _ => value,
+    };
+
+    Ok(Some(value))
+}
+
+/// Picks which `oneOf`/`anyOf` branch to generate an example from: an exact
+/// `settings.variant_selector` match first (against either the discriminator
+/// mapping key or the referenced schema's own name), then the discriminator's
+/// first mapping entry, then simply the first subschema.
+fn select_variant<'a>(
+    anyof: &'a [ObjectOrReference<Schema>],
+    discriminator: Option<&Discriminator>,
+    settings: &SpecBodySettings,
+) -> Option<(&'a ObjectOrReference<Schema>, Option<String>)> {
+    if let Some(selector) = &settings.variant_selector {
+        if let Some(obj) = anyof.iter().find(|obj| {
+            discriminator_value_for(obj, discriminator).as_deref() == Some(selector)
+                || schema_ref_name(obj).as_deref() == Some(selector)
+        }) {
+            return Some((obj, discriminator_value_for(obj, discriminator)));
+        }
     }
 
-    Ok(Some(hurl_core::ast::JsonValue::Object {
-        space0: "".to_string(),
-        elements: vec![],
-    }))
+    if let Some(mapping) = discriminator.and_then(|d| d.mapping.as_ref()) {
+        // Sorted by key so "the first mapping entry" is reproducible
+        // regardless of whether the underlying map preserves declaration
+        // order or not.
+        let mut mapping: Vec<_> = mapping.iter().collect();
+        mapping.sort_by(|a, b| a.0.cmp(b.0));
+
+        if let Some((value, ref_path)) = mapping.into_iter().next() {
+            let mapped_name = ref_path.rsplit('/').next();
+            if let Some(obj) = anyof
+                .iter()
+                .find(|obj| schema_ref_name(obj).as_deref() == mapped_name)
+            {
+                return Some((obj, Some(value.clone())));
+            }
+        }
+    }
+
+    anyof
+        .first()
+        .map(|obj| (obj, discriminator_value_for(obj, discriminator)))
+}
+
+/// The component schema name a `$ref` points at (e.g. `Cat` for
+/// `#/components/schemas/Cat`). Inline (non-`$ref`) variants have none.
+fn schema_ref_name(obj: &ObjectOrReference<Schema>) -> Option<String> {
+    match obj {
+        ObjectOrReference::Ref { ref_path } => ref_path.rsplit('/').next().map(str::to_string),
+        ObjectOrReference::Object(_) => None,
+    }
+}
+
+/// The discriminator value that should be written onto a generated variant:
+/// the mapping key pointing at it, if any, otherwise its own schema name.
+fn discriminator_value_for(
+    obj: &ObjectOrReference<Schema>,
+    discriminator: Option<&Discriminator>,
+) -> Option<String> {
+    let name = schema_ref_name(obj)?;
+
+    if let Some(mapping) = discriminator.and_then(|d| d.mapping.as_ref()) {
+        if let Some((value, _)) = mapping
+            .iter()
+            .find(|(_, ref_path)| ref_path.rsplit('/').next() == Some(name.as_str()))
+        {
+            return Some(value.clone());
+        }
+    }
+
+    Some(name)
+}
+
+/// Writes the discriminator property onto a generated variant object: if the
+/// variant already declared it (most commonly via an `allOf` base schema,
+/// which generates it with a generic placeholder value), the existing
+/// element's value is replaced rather than appending a second, duplicate
+/// `property_name` key.
+fn set_discriminator_property(
+    value: hurl_core::ast::JsonValue,
+    property_name: &str,
+    discriminator_value: &str,
+    depth: usize,
+    settings: &SpecBodySettings,
+) -> hurl_core::ast::JsonValue {
+    match value {
+        hurl_core::ast::JsonValue::Object { space0, mut elements } => {
+            let element = build_json_object_element(
+                template_from_string(property_name),
+                hurl_core::ast::JsonValue::String(template_from_string(discriminator_value)),
+                depth,
+                &settings.formatting,
+            );
+
+            match elements
+                .iter_mut()
+                .find(|existing| existing.name.to_string() == property_name)
+            {
+                Some(existing) => *existing = element,
+                None => elements.push(element),
+            }
+
+            hurl_core::ast::JsonValue::Object { space0, elements }
+        }
+        other => other,
+    }
 }
 
 fn json_obj_from_allof(
     allof: Vec<ObjectOrReference<Schema>>,
     spec: &Spec,
     depth: usize,
+    path: &str,
     settings: &SpecBodySettings,
+    active_refs: &mut RefStack,
 ) -> Result<hurl_core::ast::JsonValue, RefError> {
+    let resolved = allof
+        .into_iter()
+        .map(|schema| schema.resolve(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // A property is required if any branch of the allOf lists it, so the
+    // required sets are accumulated across every subschema up front, the
+    // same way a flattened struct's required membership would be computed.
+    let required: Vec<String> = resolved
+        .iter()
+        .flat_map(|schema| schema.required.clone())
+        .collect();
+
     let mut properties = vec![];
-    for schema in allof {
-        for prop in schema.resolve(spec)?.properties {
-            let value = parse_json_from_schema(prop.1.resolve(spec)?, spec, depth + 1, &settings)?;
+    for schema in resolved {
+        for prop in properties_in_stable_order(&schema) {
+            if !settings.includes_field(&prop.0, &required) {
+                continue;
+            }
+
+            let prop_path = format!("{}.{}", path, prop.0);
+            let value = match resolve_for_recursion(prop.1, spec, active_refs)? {
+                Some((s, ref_path)) => {
+                    let v = parse_json_from_schema(
+                        s,
+                        spec,
+                        depth + 1,
+                        &prop_path,
+                        settings,
+                        active_refs,
+                    )?;
+                    leave_ref(active_refs, ref_path);
+                    v
+                }
+                None => Some(hurl_core::ast::JsonValue::Null),
+            };
             match value {
                 Some(v) => properties.push(build_json_object_element(
                     template_from_string(&prop.0),